@@ -1,8 +1,22 @@
 // SPDX-License-Identifier: MIT
 
+// `fixed_size_block` is the default design, always available. `bump` and
+// `slab` are opt-in designs selected at compile time via the matching
+// Cargo feature; see the `#[global_allocator]` static in main.rs.
+#[cfg(feature = "bump-allocator")]
+pub mod bump;
+pub mod fixed_size_block;
 pub mod global_impl;
+#[cfg(feature = "slab-allocator")]
+pub mod slab;
 
+#[cfg(feature = "bump-allocator")]
+pub use self::bump::BumpAllocator;
+pub use self::fixed_size_block::FixedSizeBlockAllocator;
 pub use self::global_impl::Locked;
+#[cfg(feature = "slab-allocator")]
+pub use self::slab::Heap;
+use core::alloc::Layout;
 use core::mem;
 use core::ptr;
 
@@ -97,7 +111,9 @@ impl LinkedListAllocator {
         None
     }
 
-    /// Adds a chunk of memory to the free list, maintaining address order.
+    /// Adds a chunk of memory to the free list, maintaining address order
+    /// and coalescing with adjacent free regions so repeated alloc/dealloc
+    /// cycles don't splinter the heap into ever-smaller nodes.
     /// # Safety
     /// The memory range must be valid and its ownership must be transferred here.
     unsafe fn add_free_region(&mut self, addr: usize, size: usize) {
@@ -105,24 +121,6 @@ impl LinkedListAllocator {
         let current_size = addr + size - aligned_addr;
         let mut current = &mut self.head;
 
-        // Discard the region if it's too small to even hold a ListNode header.
-        if current_size < mem::size_of::<ListNode>() {
-            return;
-        }
-
-        let new_node_ptr = aligned_addr as *mut ListNode;
-        let new_node = unsafe {
-            // Write the new node into the start of the free region.
-            ptr::write(
-                new_node_ptr,
-                ListNode {
-                    size: current_size,
-                    next: None,
-                },
-            );
-            &mut *new_node_ptr
-        };
-
         // Traverse the list to find the correct insertion point (sorted by address).
         while let Some(ref mut node) = current.next {
             if node.start_addr() > aligned_addr {
@@ -131,9 +129,53 @@ impl LinkedListAllocator {
             current = current.next.as_mut().unwrap();
         }
 
-        // Insert the new node between 'current' and 'current.next'.
-        new_node.next = current.next.take();
-        current.next = Some(new_node);
+        // If the previous node butts right up against the freed region
+        // (zero gap), grow it in place instead of inserting a new node.
+        // This needs no header of its own, so it works even for regions
+        // smaller than a ListNode. `current.size > 0` excludes the sentinel
+        // head, which must never be treated as a real, absorbable block.
+        let merged_into_prev = current.size > 0 && current.end_addr() == aligned_addr;
+        if merged_into_prev {
+            current.size += current_size;
+        } else {
+            // Can't fold into prev, so the region needs its own ListNode
+            // header: discard it if it's too small to hold one.
+            if current_size < mem::size_of::<ListNode>() {
+                return;
+            }
+
+            let new_node_ptr = aligned_addr as *mut ListNode;
+            unsafe {
+                ptr::write(
+                    new_node_ptr,
+                    ListNode {
+                        size: current_size,
+                        next: current.next.take(),
+                    },
+                );
+                current.next = Some(&mut *new_node_ptr);
+            }
+        }
+
+        // `node` is whichever node now represents the freed region: `current`
+        // itself if we grew it, or the node we just linked in otherwise.
+        let node = if merged_into_prev {
+            &mut *current
+        } else {
+            current.next.as_mut().unwrap()
+        };
+
+        // Check the other neighbor too: if `node` butts right up against its
+        // successor, splice the successor out and fold its size in.
+        let merge_next = match node.next {
+            Some(ref next_node) => node.end_addr() == next_node.start_addr(),
+            None => false,
+        };
+        if merge_next {
+            let absorbed = node.next.take().unwrap();
+            node.size += absorbed.size;
+            node.next = absorbed.next.take();
+        }
     }
 }
 
@@ -200,3 +242,17 @@ pub fn align_up(addr: usize, align: usize) -> usize {
         addr + align - reminder
     }
 }
+
+/// Computes the `(size, align)` the `LinkedListAllocator` actually reserves
+/// for a request, so every block that leaves it is guaranteed big enough
+/// to later hold a `ListNode` header and be re-linked into the free list.
+/// `alloc` and `dealloc` must agree on this, or the size handed back to
+/// `add_free_region` won't match what `find_region` carved out.
+fn size_align(layout: Layout) -> (usize, usize) {
+    let layout = layout
+        .align_to(mem::align_of::<ListNode>())
+        .expect("adjusting alignment failed")
+        .pad_to_align();
+    let size = layout.size().max(mem::size_of::<ListNode>());
+    (size, layout.align())
+}
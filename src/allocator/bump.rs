@@ -0,0 +1,67 @@
+// SPDX-License-Identifier: MIT
+
+use super::align_up;
+use core::alloc::Layout;
+use core::ptr;
+
+/// Zero-fragmentation allocator that moves a pointer forward on every
+/// allocation and only reclaims memory once every allocation has been freed.
+/// Useful during early boot, before the free-list allocators are trusted,
+/// and as a speed baseline to compare the linked-list and fixed-size-block
+/// designs against.
+pub struct BumpAllocator {
+    heap_start: usize,
+    heap_end: usize,
+    next: usize,
+    allocations: usize,
+}
+
+impl BumpAllocator {
+    /// Creates an empty allocator with no managed memory.
+    pub const fn new() -> Self {
+        Self {
+            heap_start: 0,
+            heap_end: 0,
+            next: 0,
+            allocations: 0,
+        }
+    }
+
+    /// Initializes the allocator with a raw memory range.
+    /// # Safety
+    /// The caller must ensure the memory range is valid and not used elsewhere.
+    pub unsafe fn init(&mut self, heap_start: usize, heap_size: usize) {
+        self.heap_start = heap_start;
+        self.heap_end = heap_start + heap_size;
+        self.next = heap_start;
+        self.allocations = 0;
+    }
+
+    /// Aligns `next` up to `layout`'s alignment and advances it by
+    /// `layout.size()`, returning the old `next` on success or null if the
+    /// allocation would run past `heap_end`.
+    pub(crate) fn alloc(&mut self, layout: Layout) -> *mut u8 {
+        let alloc_start = align_up(self.next, layout.align());
+        let alloc_end = match alloc_start.checked_add(layout.size()) {
+            Some(end) => end,
+            None => return ptr::null_mut(),
+        };
+
+        if alloc_end > self.heap_end {
+            return ptr::null_mut();
+        }
+
+        self.next = alloc_end;
+        self.allocations += 1;
+        alloc_start as *mut u8
+    }
+
+    /// Decrements the live allocation count and, only once it reaches zero,
+    /// resets `next` back to `heap_start` to reclaim the whole heap at once.
+    pub(crate) fn dealloc(&mut self, _ptr: *mut u8, _layout: Layout) {
+        self.allocations -= 1;
+        if self.allocations == 0 {
+            self.next = self.heap_start;
+        }
+    }
+}
@@ -0,0 +1,127 @@
+// SPDX-License-Identifier: MIT
+
+use super::{align_up, LinkedListAllocator, WORD_64};
+use core::alloc::Layout;
+use core::mem;
+use core::ptr;
+
+/// Node of a per-size-class free list. Unlike the fallback allocator's
+/// `ListNode`, this one doesn't need to carry a `size` field: the size is
+/// implied by which `BLOCK_SIZES` slot the list lives in.
+struct ListNode {
+    next: Option<&'static mut ListNode>,
+}
+
+/// The block sizes served by the fast path, smallest to largest. Every
+/// request is rounded up to the first size here that fits it.
+const BLOCK_SIZES: &[usize] = &[8, 16, 32, 64, 128, 256, 512, 1024, 2048];
+
+const _: () = assert!(
+    BLOCK_SIZES[0] >= WORD_64,
+    "smallest block class must be able to hold a ListNode pointer"
+);
+
+/// Picks the index of the smallest block size able to satisfy `size`,
+/// or `None` if the request is bigger than our largest class.
+fn list_index(size: usize) -> Option<usize> {
+    BLOCK_SIZES.iter().position(|&s| s >= size)
+}
+
+/// Fast front-end allocator that keeps one singly-linked free list per
+/// power-of-two block size. `alloc`/`dealloc` on a non-empty list are O(1);
+/// anything that doesn't fit a class, or that hits an empty list, falls
+/// back to `fallback_allocator`.
+pub struct FixedSizeBlockAllocator {
+    list_heads: [Option<&'static mut ListNode>; BLOCK_SIZES.len()],
+    fallback_allocator: LinkedListAllocator,
+}
+
+impl FixedSizeBlockAllocator {
+    /// Creates an empty allocator with no managed memory.
+    pub const fn new() -> Self {
+        const EMPTY: Option<&'static mut ListNode> = None;
+        Self {
+            list_heads: [EMPTY; BLOCK_SIZES.len()],
+            fallback_allocator: LinkedListAllocator::new(),
+        }
+    }
+
+    /// Initializes the allocator with a raw memory range. All memory is
+    /// handed to the fallback allocator up front; block lists are
+    /// populated lazily as the fast path carves blocks out of it.
+    /// # Safety
+    /// The caller must ensure the memory range is valid and not used elsewhere.
+    pub unsafe fn init(&mut self, heap_start: usize, heap_size: usize) {
+        unsafe {
+            self.fallback_allocator.init(heap_start, heap_size);
+        }
+    }
+
+    /// Carves a fresh, `size`-aligned block of `size` bytes out of the
+    /// fallback allocator for use as a new free-list cell.
+    fn fallback_alloc(&mut self, size: usize) -> *mut u8 {
+        let block_size = align_up(size, WORD_64);
+        match self
+            .fallback_allocator
+            .find_region(block_size, block_size)
+        {
+            Some(addr) => addr as *mut u8,
+            None => ptr::null_mut(),
+        }
+    }
+
+    /// Rounds a request up to the block size of the class that will serve
+    /// it, falling back to the raw `(size, align)` pair when the request
+    /// is too large for any class.
+    pub(crate) fn alloc(&mut self, layout: Layout) -> *mut u8 {
+        let required_size = layout.size().max(layout.align());
+
+        match list_index(required_size) {
+            Some(index) => match self.list_heads[index].take() {
+                Some(node) => {
+                    self.list_heads[index] = node.next.take();
+                    node as *mut ListNode as *mut u8
+                }
+                None => {
+                    // List is empty: carve a new block from the fallback.
+                    self.fallback_alloc(BLOCK_SIZES[index])
+                }
+            },
+            // Too big for any class: go straight to the fallback allocator.
+            None => match self.fallback_allocator.find_region(layout.size(), layout.align()) {
+                Some(addr) => addr as *mut u8,
+                None => ptr::null_mut(),
+            },
+        }
+    }
+
+    /// Returns a block to its owning free list, or back to the fallback
+    /// allocator when the original request didn't match any block class.
+    ///
+    /// # Safety
+    /// `ptr` must have been returned by a previous call to `alloc` on this
+    /// allocator with the same `layout`.
+    pub(crate) unsafe fn dealloc(&mut self, ptr: *mut u8, layout: Layout) {
+        let required_size = layout.size().max(layout.align());
+
+        match list_index(required_size) {
+            Some(index) => {
+                let block_size = BLOCK_SIZES[index];
+                debug_assert!(mem::size_of::<ListNode>() <= block_size);
+                debug_assert!(mem::align_of::<ListNode>() <= block_size);
+
+                let new_node_ptr = ptr as *mut ListNode;
+                unsafe {
+                    new_node_ptr.write(ListNode {
+                        next: self.list_heads[index].take(),
+                    });
+                    self.list_heads[index] = Some(&mut *new_node_ptr);
+                }
+            }
+            None => unsafe {
+                self.fallback_allocator
+                    .add_free_region(ptr as usize, layout.size());
+            },
+        }
+    }
+}
@@ -1,4 +1,8 @@
-use super::LinkedListAllocator;
+#[cfg(feature = "bump-allocator")]
+use super::BumpAllocator;
+#[cfg(feature = "slab-allocator")]
+use super::Heap;
+use super::{size_align, FixedSizeBlockAllocator, LinkedListAllocator};
 use core::alloc::{GlobalAlloc, Layout};
 use core::ptr;
 
@@ -35,8 +39,11 @@ unsafe impl GlobalAlloc for Locked<LinkedListAllocator> {
         // Acquire the lock to ensure exclusive access to the linked list.
         let mut allocator = self.lock();
 
+        // Round up so the reserved block can always later hold a ListNode.
+        let (size, align) = size_align(layout);
+
         // Attempt to find a region that matches the requested size and alignment.
-        match allocator.find_region(layout.size(), layout.align()) {
+        match allocator.find_region(size, align) {
             Some(addr) => addr as *mut u8, // Success: return the raw pointer.
             None => ptr::null_mut(),       // Failure: return null pointer (OOM).
         }
@@ -48,10 +55,72 @@ unsafe impl GlobalAlloc for Locked<LinkedListAllocator> {
         // Acquire the lock before modifying the list nodes.
         let mut allocator = self.lock();
 
+        // Apply the identical rounding `alloc` used, so the size handed back
+        // here always matches what was actually reserved.
+        let (size, _align) = size_align(layout);
+
         // Safety: We trust the pointer and layout provided by the Rust compiler.
         // We cast the pointer back to a numerical address and re-add it as a free region.
         unsafe {
-            allocator.add_free_region(ptr as usize, layout.size());
+            allocator.add_free_region(ptr as usize, size);
+        }
+    }
+}
+
+/// Implementation of the `GlobalAlloc` trait for the fast front-end
+/// allocator. This is the design installed as `#[global_allocator]` in
+/// main.rs by default (no allocator feature selected).
+unsafe impl GlobalAlloc for Locked<FixedSizeBlockAllocator> {
+    /// Serves the request from the matching block-size free list, falling
+    /// back to the inner `LinkedListAllocator` when that list is empty or
+    /// the request is too large for any block class.
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        self.lock().alloc(layout)
+    }
+
+    /// Pushes the block back onto its owning free list, or returns it to
+    /// the fallback allocator when it never belonged to a block class.
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe {
+            self.lock().dealloc(ptr, layout);
         }
     }
 }
+
+/// Implementation of the `GlobalAlloc` trait for the slab allocator. Only
+/// compiled in, and only installed as `#[global_allocator]` in main.rs,
+/// when the crate is built with `--features slab-allocator`.
+#[cfg(feature = "slab-allocator")]
+unsafe impl GlobalAlloc for Locked<Heap> {
+    /// Serves the request from the matching slab's free-list stack,
+    /// growing that slab or falling back to the `LinkedListAllocator` as
+    /// needed.
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        self.lock().alloc(layout)
+    }
+
+    /// Recovers the owning slab from `ptr`'s address and pushes the cell
+    /// back onto its stack, or returns it to the fallback allocator.
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe {
+            self.lock().dealloc(ptr, layout);
+        }
+    }
+}
+
+/// Implementation of the `GlobalAlloc` trait for the bump allocator. Only
+/// compiled in, and only installed as `#[global_allocator]` in main.rs,
+/// when the crate is built with `--features bump-allocator`.
+#[cfg(feature = "bump-allocator")]
+unsafe impl GlobalAlloc for Locked<BumpAllocator> {
+    /// Bumps `next` forward by `layout.size()`, aligned to `layout.align()`.
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        self.lock().alloc(layout)
+    }
+
+    /// Drops the live allocation count, resetting the whole heap once it
+    /// hits zero.
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        self.lock().dealloc(ptr, layout);
+    }
+}
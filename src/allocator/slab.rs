@@ -0,0 +1,248 @@
+// SPDX-License-Identifier: MIT
+
+use super::{align_up, LinkedListAllocator};
+use core::alloc::Layout;
+use core::mem;
+use core::ptr;
+
+/// Number of power-of-two object classes served by the slab allocator.
+pub const NUM_OF_SLABS: usize = 8;
+
+/// Minimum size of the contiguous arena backing a single slab. Slabs whose
+/// block size is larger than this just grow one block at a time instead.
+pub const MIN_SLAB_SIZE: usize = 4096;
+
+/// How many times a single slab is allowed to grow before we give up and
+/// return null. Each growth costs one entry here to remember the arena's
+/// address range for `dealloc`'s lookup; 64 is generous enough for a
+/// long-running kernel without resorting to a dynamically-sized table.
+const MAX_ARENAS_PER_SLAB: usize = 64;
+
+/// Object classes, smallest to largest. `alloc` rounds a request up to the
+/// first class here that fits it.
+const SLAB_SIZES: [usize; NUM_OF_SLABS] = [64, 128, 256, 512, 1024, 2048, 4096, 8192];
+
+/// Node of a slab's intrusive free list. Just like the other allocators in
+/// this crate, the `next` pointer lives inside the freed cell itself.
+struct ListNode {
+    next: Option<&'static mut ListNode>,
+}
+
+/// One object class: a block size, the arenas backing it, and the free
+/// list threaded through their unused cells.
+struct Slab {
+    block_size: usize,
+    free_list: Option<&'static mut ListNode>,
+    /// Address ranges `[start, end)` of every arena this slab owns, used by
+    /// `dealloc` to recover which slab a pointer was allocated from.
+    arenas: [(usize, usize); MAX_ARENAS_PER_SLAB],
+    arena_count: usize,
+}
+
+impl Slab {
+    const fn new(block_size: usize) -> Self {
+        Self {
+            block_size,
+            free_list: None,
+            arenas: [(0, 0); MAX_ARENAS_PER_SLAB],
+            arena_count: 0,
+        }
+    }
+
+    /// Size of a fresh arena for this slab: at least `MIN_SLAB_SIZE`, and at
+    /// least one block, rounded up to a whole number of blocks so the arena
+    /// can be partitioned into cells with no leftover.
+    fn arena_size(&self) -> usize {
+        let min = MIN_SLAB_SIZE.max(self.block_size);
+        align_up(min, self.block_size)
+    }
+
+    /// Partitions `[addr, addr + size)` into `block_size` cells and threads
+    /// them onto the free list, remembering the range for address lookups.
+    ///
+    /// # Safety
+    /// The range must be valid, word-aligned memory not used elsewhere.
+    unsafe fn populate(&mut self, addr: usize, size: usize) {
+        let cell_count = size / self.block_size;
+        for i in 0..cell_count {
+            let cell_addr = addr + i * self.block_size;
+            let cell_ptr = cell_addr as *mut ListNode;
+            unsafe {
+                ptr::write(
+                    cell_ptr,
+                    ListNode {
+                        next: self.free_list.take(),
+                    },
+                );
+                self.free_list = Some(&mut *cell_ptr);
+            }
+        }
+
+        self.arenas[self.arena_count] = (addr, addr + size);
+        self.arena_count += 1;
+    }
+
+    /// Whether `addr` falls inside any arena this slab owns.
+    fn owns(&self, addr: usize) -> bool {
+        self.arenas[..self.arena_count]
+            .iter()
+            .any(|&(start, end)| addr >= start && addr < end)
+    }
+}
+
+/// Redox-style slab allocator: `NUM_OF_SLABS` object classes, each an
+/// intrusive free-list stack over one or more fixed-size arenas, with a
+/// `LinkedListAllocator` fallback for oversized requests and for growing
+/// slabs on exhaustion. `alloc`/`dealloc` are O(1) on the fast path;
+/// deallocation never merges cells across classes, it just returns them to
+/// the owning slab's stack.
+pub struct Heap {
+    slabs: [Slab; NUM_OF_SLABS],
+    fallback_allocator: LinkedListAllocator,
+}
+
+impl Heap {
+    /// Creates an empty heap with no managed memory.
+    pub const fn new() -> Self {
+        Self {
+            slabs: [
+                Slab::new(SLAB_SIZES[0]),
+                Slab::new(SLAB_SIZES[1]),
+                Slab::new(SLAB_SIZES[2]),
+                Slab::new(SLAB_SIZES[3]),
+                Slab::new(SLAB_SIZES[4]),
+                Slab::new(SLAB_SIZES[5]),
+                Slab::new(SLAB_SIZES[6]),
+                Slab::new(SLAB_SIZES[7]),
+            ],
+            fallback_allocator: LinkedListAllocator::new(),
+        }
+    }
+
+    /// Initializes the heap with a raw memory range: the whole range is
+    /// handed to the fallback allocator, then each slab eagerly carves out
+    /// and partitions its first arena.
+    /// # Safety
+    /// The caller must ensure the memory range is valid and not used elsewhere.
+    pub unsafe fn init(&mut self, heap_start: usize, heap_size: usize) {
+        unsafe {
+            self.fallback_allocator.init(heap_start, heap_size);
+            for index in 0..NUM_OF_SLABS {
+                self.grow_slab(index);
+            }
+        }
+    }
+
+    /// Index of the smallest slab whose block size can satisfy `size`, or
+    /// `None` if the request is bigger than the largest class.
+    fn slab_index(size: usize) -> Option<usize> {
+        SLAB_SIZES.iter().position(|&s| s >= size)
+    }
+
+    /// Carves a fresh, page-aligned arena out of the fallback allocator and
+    /// partitions it into cells for `self.slabs[index]`. Returns `false` if
+    /// the slab has exhausted its arena budget or the fallback is out of
+    /// memory.
+    ///
+    /// # Safety
+    /// `index` must be a valid slab index.
+    unsafe fn grow_slab(&mut self, index: usize) -> bool {
+        let slab = &mut self.slabs[index];
+        if slab.arena_count == slab.arenas.len() {
+            return false;
+        }
+
+        // Align the arena to the slab's own block size, not just
+        // MIN_SLAB_SIZE: for classes above 4096 (e.g. the 8192 class) that
+        // would under-align the arena and every cell `populate` carves out
+        // of it, breaking the "cell address aligned to block size"
+        // invariant and, transitively, `GlobalAlloc`'s alignment contract.
+        let arena_size = slab.arena_size();
+        let arena_align = slab.block_size.max(MIN_SLAB_SIZE);
+        match self
+            .fallback_allocator
+            .find_region(arena_size, arena_align)
+        {
+            Some(addr) => {
+                unsafe {
+                    slab.populate(addr, arena_size);
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Finds the slab that owns `addr`, if any.
+    fn slab_owning(&self, addr: usize) -> Option<usize> {
+        self.slabs.iter().position(|slab| slab.owns(addr))
+    }
+
+    /// Rounds the request up to an object class and pops a cell from its
+    /// free list, growing the slab on exhaustion; requests larger than the
+    /// biggest class go straight to the fallback allocator.
+    pub(crate) fn alloc(&mut self, layout: Layout) -> *mut u8 {
+        let required_size = layout.size().max(layout.align());
+
+        let index = match Self::slab_index(required_size) {
+            Some(index) => index,
+            None => {
+                return match self
+                    .fallback_allocator
+                    .find_region(layout.size(), layout.align())
+                {
+                    Some(addr) => addr as *mut u8,
+                    None => ptr::null_mut(),
+                };
+            }
+        };
+
+        if self.slabs[index].free_list.is_none() {
+            unsafe {
+                if !self.grow_slab(index) {
+                    return ptr::null_mut();
+                }
+            }
+        }
+
+        match self.slabs[index].free_list.take() {
+            Some(node) => {
+                self.slabs[index].free_list = node.next.take();
+                node as *mut ListNode as *mut u8
+            }
+            None => ptr::null_mut(),
+        }
+    }
+
+    /// Recovers which slab `ptr` was allocated from by address range and
+    /// pushes the cell back onto that slab's free-list stack; pointers that
+    /// don't belong to any slab are returned to the fallback allocator.
+    ///
+    /// # Safety
+    /// `ptr` must have been returned by a previous call to `alloc` on this
+    /// heap with the same `layout`.
+    pub(crate) unsafe fn dealloc(&mut self, ptr: *mut u8, layout: Layout) {
+        match self.slab_owning(ptr as usize) {
+            Some(index) => {
+                let slab = &mut self.slabs[index];
+                debug_assert!(mem::size_of::<ListNode>() <= slab.block_size);
+                debug_assert!(mem::align_of::<ListNode>() <= slab.block_size);
+
+                let node_ptr = ptr as *mut ListNode;
+                unsafe {
+                    ptr::write(
+                        node_ptr,
+                        ListNode {
+                            next: slab.free_list.take(),
+                        },
+                    );
+                    slab.free_list = Some(&mut *node_ptr);
+                }
+            }
+            None => unsafe {
+                self.fallback_allocator
+                    .add_free_region(ptr as usize, layout.size());
+            },
+        }
+    }
+}
@@ -19,9 +19,18 @@ core::arch::global_asm!(include_str!("../boot.s"));
 mod allocator;
 mod uart;
 
-use crate::allocator::{LinkedListAllocator, Locked};
+#[cfg(feature = "bump-allocator")]
+use crate::allocator::BumpAllocator;
+#[cfg(not(any(feature = "slab-allocator", feature = "bump-allocator")))]
+use crate::allocator::FixedSizeBlockAllocator;
+#[cfg(feature = "slab-allocator")]
+use crate::allocator::Heap;
+use crate::allocator::Locked;
 use crate::uart::UART;
 
+#[cfg(all(feature = "bump-allocator", feature = "slab-allocator"))]
+compile_error!("features \"bump-allocator\" and \"slab-allocator\" are mutually exclusive");
+
 // These symbols are defined in our linker script (linker.ld).
 // We don't care about their value, only their address in memory.
 
@@ -29,10 +38,24 @@ unsafe extern "C" {
     static _heap_start: u8;
 }
 
-/// The global memory allocator. We wrap our LinkedListAllocator in a
-/// spinlock-based Locked wrapper to ensure thread-safe access (essential for multicore).
+// The global memory allocator, wrapped in a spinlock-based Locked for
+// thread-safe access (essential for multicore). Swapping designs is a
+// compile-time choice, since every allocator plugs into the same
+// `Locked<A>` / `GlobalAlloc` machinery:
+//   - default:                      FixedSizeBlockAllocator
+//   - `--features slab-allocator`:  Heap, the Redox-style slab allocator
+//   - `--features bump-allocator`:  BumpAllocator, a frees-all-at-once design
+#[cfg(feature = "slab-allocator")]
+#[global_allocator]
+static ALLOCATOR: Locked<Heap> = Locked::new(Heap::new());
+
+#[cfg(feature = "bump-allocator")]
+#[global_allocator]
+static ALLOCATOR: Locked<BumpAllocator> = Locked::new(BumpAllocator::new());
+
+#[cfg(not(any(feature = "slab-allocator", feature = "bump-allocator")))]
 #[global_allocator]
-static ALLOCATOR: Locked<LinkedListAllocator> = Locked::new(LinkedListAllocator::new());
+static ALLOCATOR: Locked<FixedSizeBlockAllocator> = Locked::new(FixedSizeBlockAllocator::new());
 
 /// This handler is called when the allocator fails to find a suitable memory region.
 #[alloc_error_handler]